@@ -1,8 +1,19 @@
 #![no_std]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::vec::Vec;
 use core::{marker::PhantomData, mem, ptr, slice};
 use libc::c_int;
 
+#[cfg(feature = "std")]
+mod read;
+#[cfg(feature = "std")]
+pub use read::ReadDecoder;
+
 /// Raw minimp3 bindings if you need them,
 /// although if there's a desired feature please make an issue/PR.
 pub mod ffi {
@@ -27,31 +38,117 @@ pub const MAX_SAMPLES_PER_FRAME: usize = ffi::MINIMP3_MAX_SAMPLES_PER_FRAME as u
 pub struct Decoder<'a> {
     ffi_frame: ffi::mp3dec_frame_info_t,
     instance: ffi::mp3dec_t,
-    pcm: [Sample; MAX_SAMPLES_PER_FRAME],
+
+    // Scratch buffers for `next_frame_i16`/`next_frame_f32`, which are available
+    // regardless of the "float" feature (the peek variants don't decode and use
+    // neither). Whichever of the two matches the feature-selected `Sample` is also
+    // what `next_frame` decodes into directly; the other is filled by rescaling,
+    // see `decode_i16`/`decode_f32`.
+    pcm_i16: [i16; MAX_SAMPLES_PER_FRAME],
+    pcm_f32: [f32; MAX_SAMPLES_PER_FRAME],
 
     // cache for peek/skip_frame, should be set to None upon any seeking otherwise it'll get stale
     cached_len: Option<usize>,
 
+    // `StreamInfo` parsed from the stream's first frame, cached on the first call to
+    // `info()` so later calls (in particular the ones `seek_to_sample`/`seek_to_duration`
+    // make internally) keep returning it even after the decoder has moved past frame 0.
+    // The outer `Option` is "not parsed yet"; the inner one is "parsed, no header found".
+    info: Option<Option<StreamInfo>>,
+    // `sample_count`/`channels` of that same first frame, cached alongside `info`
+    // since both come from the same peek. Kept separate (rather than pre-multiplied)
+    // because `seek_to_sample` needs samples summed across channels while
+    // `seek_to_duration` needs per-channel samples, to match `sample_rate`.
+    first_frame_sample_count: Option<u64>,
+    first_frame_channels: Option<u64>,
+
+    // Gapless playback state for `next_frame_gapless`, lazily discovered from the
+    // stream's Xing/LAME tag on its first call and otherwise untouched.
+    gapless: Option<GaplessInfo>,
+    gapless_frame_samples: Option<u32>,
+    gapless_emitted: u64,
+
+    data: &'a [u8],
     data_offset: usize,
     data_ptr: *const u8,
     data_rem_len: usize,
     _phantom: PhantomData<&'a [u8]>,
 }
 
+/// Seek/duration info parsed from a Xing/Info or VBRI header embedded in the
+/// stream's first audio frame, see [Decoder::info](struct.Decoder.html#method.info).
+#[derive(Debug, Clone)]
+pub struct StreamInfo {
+    /// Total number of frames in the stream, if the header reports it.
+    pub frame_count: Option<u32>,
+
+    /// Total number of bytes in the (audio portion of the) stream, if the header reports it.
+    pub byte_count: Option<u32>,
+
+    /// Encoder delay in samples, from a LAME tag following a Xing/Info header, if present.
+    /// See [Decoder::next_frame_gapless](struct.Decoder.html#method.next_frame_gapless).
+    pub encoder_delay: Option<u32>,
+
+    /// Encoder padding in samples, from a LAME tag following a Xing/Info header, if present.
+    /// See [Decoder::next_frame_gapless](struct.Decoder.html#method.next_frame_gapless).
+    pub encoder_padding: Option<u32>,
+
+    // Used to turn a fraction of the stream into a byte offset, without re-parsing
+    // the Xing TOC / VBRI entry table on every seek.
+    seek_table: SeekTable,
+}
+
+#[derive(Debug, Clone)]
+enum SeekTable {
+    /// No seek table available, seeking falls back to a linear byte estimate.
+    None,
+    /// Xing TOC: `toc[i]`, `i` in `0..100`, is the byte percentage (0-255, i.e. `/256`)
+    /// reached at the `i`-percent point of the stream's duration.
+    Xing([u8; XING_TOC_LEN]),
+    /// VBRI per-entry byte sizes, summed to find the byte offset of entry `i`,
+    /// which covers `1/len()` of the stream's duration each. Heap-allocated: VBRI's
+    /// `entries` count is a full 16-bit field and real streams commonly carry more
+    /// entries than would be cheap to keep inline in every `StreamInfo`.
+    Vbri(Vec<u32>),
+}
+
+// Encoder delay/padding (in samples) parsed from a LAME tag, see `next_frame_gapless`.
+#[derive(Debug, Clone, Copy)]
+struct GaplessInfo {
+    delay: u32,
+    padding: u32,
+    frame_count: Option<u32>,
+}
+
+const XING_TOC_LEN: usize = 100;
+
+const TAG_XING: [u8; 4] = *b"Xing";
+const TAG_INFO: [u8; 4] = *b"Info";
+const TAG_VBRI: [u8; 4] = *b"VBRI";
+// The VBRI tag sits at a fixed offset of 32 bytes past the 4-byte frame header,
+// i.e. 36 bytes from the start of the frame (unlike the Xing/Info tag, its
+// position doesn't depend on the MPEG version/channel mode side info size).
+const VBRI_OFFSET: usize = 36;
+
 /// Info about the current frame yielded by a [Decoder](struct.Decoder.html).
+///
+/// Generic over the PCM sample type `S`, which is [Sample](type.Sample.html)
+/// (`i16`, or `f32` with the "float" feature) for [next_frame](struct.Decoder.html#method.next_frame),
+/// or explicitly `i16`/`f32` for [next_frame_i16](struct.Decoder.html#method.next_frame_i16)/
+/// [next_frame_f32](struct.Decoder.html#method.next_frame_f32).
 #[derive(Debug)]
-pub struct Frame<'a> {
+pub struct Frame<'a, S = Sample> {
     /// Bitrate of the source frame in kb/s.
     pub bitrate: u32,
 
     /// Number of channels in this frame.
     pub channels: u32,
 
-    /// MPEG layer of this frame.
+    /// MPEG layer of this frame: `1` for Layer I, `2` for Layer II, `3` for Layer III.
     pub mpeg_layer: u32,
 
     /// Reference to the samples in this frame, copy if needed to allocate.
-    pub samples: &'a [Sample],
+    pub samples: &'a [S],
 
     /// Sample count per channel.
     /// Should be identical to `samples.len() / channels`
@@ -65,6 +162,17 @@ pub struct Frame<'a> {
     pub source: &'a [u8],
 }
 
+/// A chunk of data yielded by [next_frame_or_skipped](struct.Decoder.html#method.next_frame_or_skipped),
+/// distinguishing decoded audio from the non-audio bytes (ID3 tags, junk, ...)
+/// that [next_frame](struct.Decoder.html#method.next_frame) silently skips over.
+#[derive(Debug)]
+pub enum FrameKind<'a> {
+    /// A successfully decoded audio frame.
+    Audio(Frame<'a>),
+    /// Source bytes minimp3 didn't recognize as an audio frame, verbatim.
+    Other(&'a [u8]),
+}
+
 impl<'a> Decoder<'a> {
     /// Creates a decoder over `data` (mp3 bytes).
     pub fn new(data: &'a (impl AsRef<[u8]> + ?Sized)) -> Self {
@@ -76,9 +184,19 @@ impl<'a> Decoder<'a> {
                 ffi::mp3dec_init(&mut decoder);
                 decoder
             },
-            pcm: [Default::default(); MAX_SAMPLES_PER_FRAME],
+            pcm_i16: [Default::default(); MAX_SAMPLES_PER_FRAME],
+            pcm_f32: [Default::default(); MAX_SAMPLES_PER_FRAME],
             cached_len: None,
 
+            info: None,
+            first_frame_sample_count: None,
+            first_frame_channels: None,
+
+            gapless: None,
+            gapless_frame_samples: None,
+            gapless_emitted: 0,
+
+            data,
             data_offset: 0,
             data_ptr: data.as_ptr(),
             data_rem_len: data.len(),
@@ -91,7 +209,7 @@ impl<'a> Decoder<'a> {
     pub fn next_frame(&mut self) -> Option<Frame> {
         self.cached_len = None;
         unsafe {
-            let out_ptr: *mut Sample = self.pcm.as_mut_ptr();
+            let out_ptr: *mut Sample = self.native_pcm_ptr();
             let samples = self.ffi_decode_frame(out_ptr) as u32;
             let frame_bytes = self.ffi_frame.frame_bytes as usize;
             self.data_ptr = self.data_ptr.offset(frame_bytes as isize);
@@ -102,8 +220,7 @@ impl<'a> Decoder<'a> {
                     bitrate: self.ffi_frame.bitrate_kbps as u32,
                     channels: self.ffi_frame.channels as u32,
                     samples: self
-                        .pcm
-                        .get_unchecked(..(samples * self.ffi_frame.channels as u32) as usize), // todo: feature?
+                        .native_pcm_slice((samples * self.ffi_frame.channels as u32) as usize),
                     sample_rate: self.ffi_frame.hz as u32,
                     mpeg_layer: self.ffi_frame.layer as u32,
                     sample_count: samples,
@@ -120,6 +237,98 @@ impl<'a> Decoder<'a> {
         }
     }
 
+    /// Like [next_frame](struct.Decoder.html#method.next_frame), but instead of silently
+    /// skipping past non-audio data (ID3v2/APE tags, junk, ...) it hands the exact
+    /// skipped source bytes back as [FrameKind::Other](enum.FrameKind.html), one
+    /// minimp3-reported chunk at a time. Useful for tag-aware tooling or measuring
+    /// how much of a stream isn't audio, without a second pass over the data.
+    pub fn next_frame_or_skipped(&mut self) -> Option<FrameKind> {
+        self.cached_len = None;
+        unsafe {
+            let out_ptr: *mut Sample = self.native_pcm_ptr();
+            let samples = self.ffi_decode_frame(out_ptr) as u32;
+            let frame_bytes = self.ffi_frame.frame_bytes as usize;
+            if frame_bytes == 0 {
+                return None;
+            }
+            let start = self.data_ptr;
+            self.data_ptr = self.data_ptr.offset(frame_bytes as isize);
+            self.data_offset += frame_bytes;
+            self.data_rem_len -= frame_bytes;
+            if samples > 0 {
+                Some(FrameKind::Audio(Frame {
+                    bitrate: self.ffi_frame.bitrate_kbps as u32,
+                    channels: self.ffi_frame.channels as u32,
+                    samples: self
+                        .native_pcm_slice((samples * self.ffi_frame.channels as u32) as usize),
+                    sample_rate: self.ffi_frame.hz as u32,
+                    mpeg_layer: self.ffi_frame.layer as u32,
+                    sample_count: samples,
+                    source: slice::from_raw_parts(start, frame_bytes),
+                }))
+            } else {
+                Some(FrameKind::Other(slice::from_raw_parts(start, frame_bytes)))
+            }
+        }
+    }
+
+    /// Like [next_frame](struct.Decoder.html#method.next_frame), but trims the
+    /// encoder delay/padding a LAME tag reports (see [StreamInfo](struct.StreamInfo.html)),
+    /// so concatenated/looped gapless MP3s don't play back the encoder's silent
+    /// priming and trailing frames. Falls back to plain `next_frame` behavior for
+    /// streams without a LAME tag.
+    ///
+    /// The delay/padding sample counts can span more than one frame; fully-trimmed
+    /// frames are skipped over transparently, same as non-audio data.
+    pub fn next_frame_gapless(&mut self) -> Option<Frame> {
+        let gapless = self.ensure_gapless();
+        if gapless.delay == 0 && gapless.padding == 0 {
+            return self.next_frame();
+        }
+
+        loop {
+            // Peek first and pull out only the plain numbers we need: holding on to
+            // the peeked `Frame` itself would keep `self` borrowed, and every branch
+            // below needs to mutate `self` (and, on the non-skip path, re-borrow it
+            // via `next_frame`).
+            let peeked = self.peek_frame()?;
+            let frame_samples = peeked.sample_count as u64;
+            let channels = peeked.channels.max(1) as u64;
+
+            let start = self.gapless_emitted;
+            let end = start + frame_samples;
+            self.gapless_emitted = end;
+
+            let keep_start = start.max(gapless.delay as u64);
+            let total_samples = gapless
+                .frame_count
+                .zip(self.gapless_frame_samples)
+                .map(|(frames, per_frame)| frames as u64 * per_frame as u64);
+            let keep_end = match total_samples {
+                Some(total) => end.min(total.saturating_sub(gapless.padding as u64)),
+                None => end,
+            };
+
+            if keep_end <= keep_start {
+                self.skip_frame(); // entirely priming delay or entirely trailing padding
+                continue;
+            }
+
+            let frame = self.next_frame()?;
+            if keep_start == start && keep_end == end {
+                return Some(frame);
+            }
+
+            let from = ((keep_start - start) * channels) as usize;
+            let to = ((keep_end - start) * channels) as usize;
+            return Some(Frame {
+                sample_count: (keep_end - keep_start) as u32,
+                samples: &frame.samples[from..to],
+                ..frame
+            });
+        }
+    }
+
     /// Reads a frame without actually decoding it or advancing.
     /// Useful when you want to, for example, calculate the audio length.
     ///
@@ -150,6 +359,119 @@ impl<'a> Decoder<'a> {
         }
     }
 
+    /// Like [next_frame](struct.Decoder.html#method.next_frame), but always decodes to `i16`
+    /// PCM regardless of the "float" feature. Lets a single build serve both `i16` and
+    /// `f32` consumers, instead of the feature picking one [Sample](type.Sample.html)
+    /// type for everyone who depends on this crate.
+    pub fn next_frame_i16(&mut self) -> Option<Frame<i16>> {
+        self.cached_len = None;
+        unsafe {
+            let samples = self.decode_i16() as u32;
+            let frame_bytes = self.ffi_frame.frame_bytes as usize;
+            self.data_ptr = self.data_ptr.offset(frame_bytes as isize);
+            self.data_offset += frame_bytes;
+            self.data_rem_len -= frame_bytes;
+            if samples > 0 {
+                Some(Frame {
+                    bitrate: self.ffi_frame.bitrate_kbps as u32,
+                    channels: self.ffi_frame.channels as u32,
+                    samples: self
+                        .pcm_i16
+                        .get_unchecked(..(samples * self.ffi_frame.channels as u32) as usize),
+                    sample_rate: self.ffi_frame.hz as u32,
+                    mpeg_layer: self.ffi_frame.layer as u32,
+                    sample_count: samples,
+                    source: slice::from_raw_parts(
+                        self.data_ptr.offset(-(frame_bytes as isize)),
+                        frame_bytes,
+                    ),
+                })
+            } else if self.ffi_frame.frame_bytes != 0 {
+                self.next_frame_i16()
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Like [next_frame_i16](struct.Decoder.html#method.next_frame_i16), but decodes to `f32` PCM.
+    pub fn next_frame_f32(&mut self) -> Option<Frame<f32>> {
+        self.cached_len = None;
+        unsafe {
+            let samples = self.decode_f32() as u32;
+            let frame_bytes = self.ffi_frame.frame_bytes as usize;
+            self.data_ptr = self.data_ptr.offset(frame_bytes as isize);
+            self.data_offset += frame_bytes;
+            self.data_rem_len -= frame_bytes;
+            if samples > 0 {
+                Some(Frame {
+                    bitrate: self.ffi_frame.bitrate_kbps as u32,
+                    channels: self.ffi_frame.channels as u32,
+                    samples: self
+                        .pcm_f32
+                        .get_unchecked(..(samples * self.ffi_frame.channels as u32) as usize),
+                    sample_rate: self.ffi_frame.hz as u32,
+                    mpeg_layer: self.ffi_frame.layer as u32,
+                    sample_count: samples,
+                    source: slice::from_raw_parts(
+                        self.data_ptr.offset(-(frame_bytes as isize)),
+                        frame_bytes,
+                    ),
+                })
+            } else if self.ffi_frame.frame_bytes != 0 {
+                self.next_frame_f32()
+            } else {
+                None
+            }
+        }
+    }
+
+    /// [peek_frame](struct.Decoder.html#method.peek_frame) counterpart of
+    /// [next_frame_i16](struct.Decoder.html#method.next_frame_i16): reads a frame's info
+    /// without decoding or advancing.
+    pub fn peek_frame_i16(&mut self) -> Option<Frame<i16>> {
+        let samples = unsafe { self.ffi_decode_frame(ptr::null_mut()) as u32 };
+        if self.ffi_frame.frame_bytes != 0 {
+            self.cached_len = Some(self.ffi_frame.frame_bytes as usize);
+            Some(Frame {
+                bitrate: self.ffi_frame.bitrate_kbps as u32,
+                channels: self.ffi_frame.channels as u32,
+                mpeg_layer: self.ffi_frame.layer as u32,
+                samples: &[],
+                sample_rate: self.ffi_frame.hz as u32,
+                sample_count: samples,
+                source: unsafe {
+                    slice::from_raw_parts(self.data_ptr, self.ffi_frame.frame_bytes as usize)
+                },
+            })
+        } else {
+            None
+        }
+    }
+
+    /// [peek_frame](struct.Decoder.html#method.peek_frame) counterpart of
+    /// [next_frame_f32](struct.Decoder.html#method.next_frame_f32): reads a frame's info
+    /// without decoding or advancing.
+    pub fn peek_frame_f32(&mut self) -> Option<Frame<f32>> {
+        let samples = unsafe { self.ffi_decode_frame(ptr::null_mut()) as u32 };
+        if self.ffi_frame.frame_bytes != 0 {
+            self.cached_len = Some(self.ffi_frame.frame_bytes as usize);
+            Some(Frame {
+                bitrate: self.ffi_frame.bitrate_kbps as u32,
+                channels: self.ffi_frame.channels as u32,
+                mpeg_layer: self.ffi_frame.layer as u32,
+                samples: &[],
+                sample_rate: self.ffi_frame.hz as u32,
+                sample_count: samples,
+                source: unsafe {
+                    slice::from_raw_parts(self.data_ptr, self.ffi_frame.frame_bytes as usize)
+                },
+            })
+        } else {
+            None
+        }
+    }
+
     /// Skips ahead one frame.
     /// The frame won't be decoded, and if peek_frame was used previously it won't even be read again.
     pub fn skip_frame(&mut self) {
@@ -165,6 +487,141 @@ impl<'a> Decoder<'a> {
         self.data_offset
     }
 
+    /// Looks for a Xing/Info or VBRI header in the stream's first frame and, if found,
+    /// returns the duration/seek info it carries. Only the very first call actually
+    /// peeks the stream; the result is cached, so calling this again after any
+    /// `next_frame`/`skip_frame`/seek still returns the same `StreamInfo` instead of
+    /// looking at whatever frame the decoder now happens to be positioned at.
+    ///
+    /// Returns `None` if the first frame has no such header, which is the case for
+    /// any file whose encoder didn't emit one; `next_frame` is always
+    /// the ground truth, this is only ever an optimization/convenience on top of it.
+    pub fn info(&mut self) -> Option<StreamInfo> {
+        if self.info.is_none() {
+            let frame = self.peek_frame();
+            let first_frame_sample_count = frame.as_ref().map(|f| f.sample_count as u64);
+            let first_frame_channels = frame.as_ref().map(|f| f.channels.max(1) as u64);
+            let info = frame.and_then(|f| StreamInfo::parse(f.source));
+            self.first_frame_sample_count = first_frame_sample_count;
+            self.first_frame_channels = first_frame_channels;
+            self.info = Some(info);
+        }
+        self.info.clone().unwrap()
+    }
+
+    /// Establishes `self.gapless`/`self.gapless_frame_samples` from the stream's
+    /// `info()` if they haven't been already, and returns the now-populated
+    /// `GaplessInfo`. Pulled out of `next_frame_gapless`'s first call so that
+    /// `seek_to_fraction` can also rely on this state existing before the caller
+    /// has ever decoded a single frame (e.g. seeking before the first
+    /// `next_frame_gapless` call).
+    fn ensure_gapless(&mut self) -> GaplessInfo {
+        if self.gapless.is_none() {
+            self.gapless = Some(
+                self.info()
+                    .map(|info| GaplessInfo {
+                        delay: info.encoder_delay.unwrap_or(0),
+                        padding: info.encoder_padding.unwrap_or(0),
+                        frame_count: info.frame_count,
+                    })
+                    .unwrap_or(GaplessInfo { delay: 0, padding: 0, frame_count: None }),
+            );
+        }
+        if self.gapless_frame_samples.is_none() {
+            self.gapless_frame_samples = self.first_frame_sample_count.map(|n| n as u32);
+        }
+        self.gapless.expect("just initialized above")
+    }
+
+    /// Seeks to the frame containing sample number `n` (of the whole stream, not
+    /// per-channel), using the seek table from [info](struct.Decoder.html#method.info)
+    /// if one is available, falling back to a linear byte-offset estimate otherwise.
+    ///
+    /// Because MP3 frame boundaries rarely line up with sample boundaries exactly,
+    /// this seeks to the frame nearest `n` and resyncs on the next `next_frame` call;
+    /// it is not exact.
+    pub fn seek_to_sample(&mut self, n: u64) -> Option<()> {
+        let info = self.info()?;
+        let frame_count = info.frame_count? as u64;
+        // The stream's actual first-frame sample count, summed across channels since
+        // `n` is "of the whole stream, not per-channel" (not the worst-case
+        // `MAX_SAMPLES_PER_FRAME`: that constant is sized for MPEG1 Layer III stereo
+        // and overstates mono/MPEG2 streams).
+        let samples_per_frame = self.first_frame_sample_count? * self.first_frame_channels?;
+        let total_samples = frame_count * samples_per_frame;
+        let fraction = if total_samples == 0 {
+            0.0
+        } else {
+            n.min(total_samples) as f32 / total_samples as f32
+        };
+        self.seek_to_fraction(&info, fraction)
+    }
+
+    /// Seeks to the frame nearest `ms` milliseconds into the stream, using the
+    /// seek table from [info](struct.Decoder.html#method.info) if one is available.
+    ///
+    /// `sample_rate` must be the stream's sample rate in Hz (available on any
+    /// already-decoded [Frame](struct.Frame.html), or by peeking one frame first).
+    pub fn seek_to_duration(&mut self, ms: u64, sample_rate: u32) -> Option<()> {
+        let info = self.info()?;
+        let frame_count = info.frame_count? as u64;
+        // Per-channel sample count this time, unlike `seek_to_sample`: `sample_rate`
+        // is already samples-per-second-per-channel, so multiplying by channels here
+        // would inflate `total_ms` by the channel count.
+        let samples_per_frame = self.first_frame_sample_count?;
+        let total_ms = frame_count * samples_per_frame * 1000 / sample_rate.max(1) as u64;
+        let fraction = if total_ms == 0 {
+            0.0
+        } else {
+            ms.min(total_ms) as f32 / total_ms as f32
+        };
+        self.seek_to_fraction(&info, fraction)
+    }
+
+    fn seek_to_fraction(&mut self, info: &StreamInfo, fraction: f32) -> Option<()> {
+        let fraction = fraction.clamp(0.0, 1.0);
+        let total_bytes = info.byte_count? as usize;
+        let byte_offset = match &info.seek_table {
+            SeekTable::None => (fraction * total_bytes as f32) as usize,
+            SeekTable::Xing(toc) => {
+                let i = ((fraction * XING_TOC_LEN as f32) as usize).min(XING_TOC_LEN - 1);
+                // `total_bytes` can be up to `u32::MAX`; widen to `u64` before the
+                // multiply so this can't overflow `usize` on 32-bit targets.
+                ((toc[i] as u64 * total_bytes as u64) / 256) as usize
+            }
+            SeekTable::Vbri(entry_bytes) => {
+                let i = ((fraction * entry_bytes.len() as f32) as usize).min(entry_bytes.len());
+                entry_bytes[..i].iter().fold(0u64, |acc, &b| acc.saturating_add(b as u64)) as usize
+            }
+        };
+        self.seek_to_byte_offset(byte_offset)?;
+
+        // `next_frame_gapless` counts samples from the start of the stream to know
+        // when it has passed the encoder delay/padding; re-estimate where that
+        // counter should be after jumping to `fraction` of the stream, rather than
+        // leaving it pointing at the pre-seek position. `ensure_gapless` establishes
+        // that state on demand so this is correct even if the caller seeks before
+        // ever calling `next_frame_gapless`.
+        let gapless = self.ensure_gapless();
+        if let (Some(per_frame), Some(frames)) = (self.gapless_frame_samples, gapless.frame_count) {
+            let total_samples = frames as u64 * per_frame as u64;
+            self.gapless_emitted = (fraction as f64 * total_samples as f64) as u64;
+        }
+        Some(())
+    }
+
+    /// Jumps the decoder straight to `offset` bytes into the original stream,
+    /// invalidating the peek cache so the next `next_frame`/`peek_frame` resyncs
+    /// on whatever frame header follows `offset`.
+    fn seek_to_byte_offset(&mut self, offset: usize) -> Option<()> {
+        let offset = offset.min(self.data.len());
+        self.cached_len = None;
+        self.data_offset = offset;
+        self.data_ptr = unsafe { self.data.as_ptr().offset(offset as isize) };
+        self.data_rem_len = self.data.len() - offset;
+        Some(())
+    }
+
     fn frame_bytes(&mut self) -> Option<usize> {
         let len = self
             .cached_len
@@ -173,6 +630,61 @@ impl<'a> Decoder<'a> {
         len
     }
 
+    #[cfg(not(feature = "float"))]
+    fn native_pcm_ptr(&mut self) -> *mut Sample {
+        self.pcm_i16.as_mut_ptr()
+    }
+    #[cfg(feature = "float")]
+    fn native_pcm_ptr(&mut self) -> *mut Sample {
+        self.pcm_f32.as_mut_ptr()
+    }
+
+    #[cfg(not(feature = "float"))]
+    unsafe fn native_pcm_slice(&self, len: usize) -> &[Sample] {
+        self.pcm_i16.get_unchecked(..len)
+    }
+    #[cfg(feature = "float")]
+    unsafe fn native_pcm_slice(&self, len: usize) -> &[Sample] {
+        self.pcm_f32.get_unchecked(..len)
+    }
+
+    // Decodes into `pcm_i16`, requantizing down from `pcm_f32` when minimp3 was
+    // built for float output (the "float" feature), since then that's the only
+    // PCM type the FFI call site actually produces.
+    #[cfg(not(feature = "float"))]
+    unsafe fn decode_i16(&mut self) -> c_int {
+        let out_ptr = self.pcm_i16.as_mut_ptr();
+        self.ffi_decode_frame(out_ptr)
+    }
+    #[cfg(feature = "float")]
+    unsafe fn decode_i16(&mut self) -> c_int {
+        let out_ptr = self.pcm_f32.as_mut_ptr();
+        let samples = self.ffi_decode_frame(out_ptr);
+        let channels = self.ffi_frame.channels.max(1) as usize;
+        for i in 0..(samples as usize * channels) {
+            self.pcm_i16[i] = (self.pcm_f32[i].clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        }
+        samples
+    }
+
+    // Decodes into `pcm_f32`, rescaling up from `pcm_i16` when minimp3 was built
+    // for `i16` output (the default, no "float" feature).
+    #[cfg(feature = "float")]
+    unsafe fn decode_f32(&mut self) -> c_int {
+        let out_ptr = self.pcm_f32.as_mut_ptr();
+        self.ffi_decode_frame(out_ptr)
+    }
+    #[cfg(not(feature = "float"))]
+    unsafe fn decode_f32(&mut self) -> c_int {
+        let out_ptr = self.pcm_i16.as_mut_ptr();
+        let samples = self.ffi_decode_frame(out_ptr);
+        let channels = self.ffi_frame.channels.max(1) as usize;
+        for i in 0..(samples as usize * channels) {
+            self.pcm_f32[i] = self.pcm_i16[i] as f32 / i16::MAX as f32;
+        }
+        samples
+    }
+
     unsafe fn ffi_decode_frame(&mut self, pcm: *mut Sample) -> c_int {
         // The minimp3 API takes `int` for size, however that won't work if
         // your file exceeds 2GB (2147483647b) in size. Thankfully,
@@ -188,3 +700,224 @@ impl<'a> Decoder<'a> {
         )
     }
 }
+
+impl StreamInfo {
+    // `frame` is the full source bytes of the first audio frame (header included),
+    // as reported by `peek_frame`/`next_frame`.
+    fn parse(frame: &[u8]) -> Option<Self> {
+        Self::parse_xing(frame).or_else(|| Self::parse_vbri(frame))
+    }
+
+    fn parse_xing(frame: &[u8]) -> Option<Self> {
+        let offset = 4 + side_info_len(frame)?;
+        let tag = frame.get(offset..offset + 4)?;
+        if tag != TAG_XING && tag != TAG_INFO {
+            return None;
+        }
+
+        let flags = read_u32(frame, offset + 4)?;
+        let mut pos = offset + 8;
+
+        let frame_count = if flags & 0x1 != 0 {
+            let v = read_u32(frame, pos)?;
+            pos += 4;
+            Some(v)
+        } else {
+            None
+        };
+        let byte_count = if flags & 0x2 != 0 {
+            let v = read_u32(frame, pos)?;
+            pos += 4;
+            Some(v)
+        } else {
+            None
+        };
+        let seek_table = if flags & 0x4 != 0 {
+            let mut toc = [0u8; XING_TOC_LEN];
+            toc.copy_from_slice(frame.get(pos..pos + XING_TOC_LEN)?);
+            pos += XING_TOC_LEN;
+            SeekTable::Xing(toc)
+        } else {
+            SeekTable::None
+        };
+        if flags & 0x8 != 0 {
+            pos += 4; // quality indicator, unused here but still occupies space before the LAME tag
+        }
+
+        // Only read delay/padding if a LAME tag is actually there, recognized by its
+        // encoder version string ("LAME", or FFmpeg's "Lavf"/"Lavc").
+        let has_lame_tag = frame
+            .get(pos..pos + 4)
+            .map_or(false, |v| v == b"LAME" || v == b"Lavf" || v == b"Lavc");
+        let (encoder_delay, encoder_padding) = if has_lame_tag {
+            parse_lame_gapless(frame, pos).unwrap_or((None, None))
+        } else {
+            (None, None)
+        };
+
+        Some(Self { frame_count, byte_count, encoder_delay, encoder_padding, seek_table })
+    }
+
+    fn parse_vbri(frame: &[u8]) -> Option<Self> {
+        let tag = frame.get(VBRI_OFFSET..VBRI_OFFSET + 4)?;
+        if tag != TAG_VBRI {
+            return None;
+        }
+
+        // Layout past the "VBRI" magic: version(2) delay(2) quality(2) bytes(4)
+        // frames(4) entries(2) scale(2) entry_bytes_size(2) entry_frames(2), then
+        // `entries` big-endian entries of `entry_bytes_size` bytes each.
+        let header = VBRI_OFFSET + 4;
+        let byte_count = read_u32(frame, header + 6)?;
+        let frame_count = read_u32(frame, header + 10)?;
+        let entry_count = read_u16(frame, header + 14)? as usize;
+        let scale = read_u16(frame, header + 16)? as u32;
+        let entry_size = read_u16(frame, header + 18)? as usize;
+
+        let mut entry_bytes = Vec::with_capacity(entry_count);
+        let mut pos = header + 22;
+        for _ in 0..entry_count {
+            entry_bytes.push(read_uint(frame, pos, entry_size)?.saturating_mul(scale));
+            pos += entry_size;
+        }
+
+        Some(Self {
+            frame_count: Some(frame_count),
+            byte_count: Some(byte_count),
+            // LAME tags (and therefore gapless info) are only ever written after a
+            // Xing/Info header, never a VBRI one.
+            encoder_delay: None,
+            encoder_padding: None,
+            seek_table: SeekTable::Vbri(entry_bytes),
+        })
+    }
+}
+
+// The LAME tag is a 9-byte encoder version string followed by fixed-size fields;
+// the delay/padding field sits after version(9) + revision/vbr(1) + lowpass(1) +
+// replay gain(8) + flags/ath(1) + bitrate(1) = 21 bytes in, and packs a 12-bit
+// delay and 12-bit padding (in samples) into 3 bytes. Not every encoder writes a
+// LAME tag, so `parse_xing` checks for its version string before calling this;
+// here `None` only means `frame` is too short to hold the field.
+fn parse_lame_gapless(frame: &[u8], lame_tag_offset: usize) -> Option<(Option<u32>, Option<u32>)> {
+    let at = lame_tag_offset + 21;
+    let b = frame.get(at..at + 3)?;
+    let delay = ((b[0] as u32) << 4) | (b[1] as u32 >> 4);
+    let padding = ((b[1] as u32 & 0x0F) << 8) | b[2] as u32;
+    Some((Some(delay), Some(padding)))
+}
+
+// MPEG1 layer III side info is 32 bytes (stereo/joint/dual) or 17 bytes (mono),
+// MPEG2/2.5 halve that to 17/9; an extra 2 bytes are present if the optional
+// 16-bit CRC follows the header. Returns `None` if `frame` is too short to
+// even contain a header.
+fn side_info_len(frame: &[u8]) -> Option<usize> {
+    let header = frame.get(0..4)?;
+    let mpeg1 = header[1] & 0x08 != 0;
+    let has_crc = header[1] & 0x01 == 0;
+    let mono = (header[3] >> 6) & 0x3 == 3;
+    let len = match (mpeg1, mono) {
+        (true, false) => 32,
+        (true, true) => 17,
+        (false, false) => 17,
+        (false, true) => 9,
+    };
+    Some(if has_crc { len + 2 } else { len })
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    let b = data.get(at..at + 4)?;
+    Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    let b = data.get(at..at + 2)?;
+    Some(u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn read_uint(data: &[u8], at: usize, len: usize) -> Option<u32> {
+    let b = data.get(at..at + len)?;
+    Some(b.iter().fold(0u32, |acc, &byte| (acc << 8) | byte as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_lame_gapless_reads_delay_and_padding_at_offset_21() {
+        let mut frame = [0u8; 24];
+        frame[21] = 0x12;
+        frame[22] = 0x34;
+        frame[23] = 0x56;
+        let (delay, padding) = parse_lame_gapless(&frame, 0).unwrap();
+        assert_eq!(delay, Some(0x123));
+        assert_eq!(padding, Some(0x456));
+    }
+
+    #[test]
+    fn parse_xing_reads_frame_count_and_byte_count() {
+        // MPEG1, stereo, no CRC -> 32-byte side info, so the Xing tag starts at 4 + 32 = 36.
+        let mut frame = [0u8; 52];
+        frame[0] = 0xFF;
+        frame[1] = 0x0B; // mpeg1 (0x08) | layer III (0x02) | no-crc (0x01)
+        frame[3] = 0x00; // stereo
+        frame[36..40].copy_from_slice(&TAG_XING);
+        frame[40..44].copy_from_slice(&0x3u32.to_be_bytes()); // frame_count + byte_count flags
+        frame[44..48].copy_from_slice(&1000u32.to_be_bytes());
+        frame[48..52].copy_from_slice(&50_000u32.to_be_bytes());
+
+        let info = StreamInfo::parse_xing(&frame).unwrap();
+        assert_eq!(info.frame_count, Some(1000));
+        assert_eq!(info.byte_count, Some(50_000));
+        assert_eq!(info.encoder_delay, None);
+        assert_eq!(info.encoder_padding, None);
+    }
+
+    #[test]
+    fn parse_vbri_reads_frame_count_and_byte_count() {
+        // The "VBRI" magic sits at a fixed offset of 36 bytes from the start of the frame.
+        let mut frame = [0u8; 66];
+        frame[36..40].copy_from_slice(&TAG_VBRI);
+        let header = 40;
+        frame[header + 6..header + 10].copy_from_slice(&60_000u32.to_be_bytes());
+        frame[header + 10..header + 14].copy_from_slice(&2000u32.to_be_bytes());
+        frame[header + 14..header + 16].copy_from_slice(&2u16.to_be_bytes()); // entry_count
+        frame[header + 16..header + 18].copy_from_slice(&1u16.to_be_bytes()); // scale
+        frame[header + 18..header + 20].copy_from_slice(&2u16.to_be_bytes()); // entry_size
+        frame[header + 22..header + 24].copy_from_slice(&100u16.to_be_bytes());
+        frame[header + 24..header + 26].copy_from_slice(&200u16.to_be_bytes());
+
+        let info = StreamInfo::parse_vbri(&frame).unwrap();
+        assert_eq!(info.frame_count, Some(2000));
+        assert_eq!(info.byte_count, Some(60_000));
+        assert_eq!(info.encoder_delay, None);
+        assert_eq!(info.encoder_padding, None);
+        match info.seek_table {
+            SeekTable::Vbri(entry_bytes) => {
+                assert_eq!(entry_bytes, alloc::vec![100, 200]);
+            }
+            _ => panic!("expected a VBRI seek table"),
+        }
+    }
+
+    #[test]
+    fn parse_vbri_keeps_every_entry_past_256_entries() {
+        const ENTRY_COUNT: usize = 300;
+        let header = 40;
+        let mut frame = alloc::vec![0u8; header + 22 + ENTRY_COUNT * 2];
+        frame[36..40].copy_from_slice(&TAG_VBRI);
+        frame[header + 14..header + 16].copy_from_slice(&(ENTRY_COUNT as u16).to_be_bytes());
+        frame[header + 16..header + 18].copy_from_slice(&1u16.to_be_bytes()); // scale
+        frame[header + 18..header + 20].copy_from_slice(&2u16.to_be_bytes()); // entry_size
+        for i in 0..ENTRY_COUNT {
+            frame[header + 22 + i * 2..header + 24 + i * 2].copy_from_slice(&1u16.to_be_bytes());
+        }
+
+        let info = StreamInfo::parse_vbri(&frame).unwrap();
+        match info.seek_table {
+            SeekTable::Vbri(entry_bytes) => assert_eq!(entry_bytes.len(), ENTRY_COUNT),
+            _ => panic!("expected a VBRI seek table"),
+        }
+    }
+}
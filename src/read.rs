@@ -0,0 +1,174 @@
+use core::{mem, slice};
+use std::io::{self, Read};
+use std::vec::Vec;
+
+use crate::{ffi, Frame, Sample, MAX_SAMPLES_PER_FRAME};
+use libc::c_int;
+
+// minimp3 may need a full frame's worth of lookahead to make progress, so the
+// buffer is kept topped up well above one frame: refill once the unconsumed
+// tail drops below `LOW_WATER`, reading up to `HIGH_WATER` bytes resident.
+const LOW_WATER: usize = MAX_SAMPLES_PER_FRAME * 8;
+const HIGH_WATER: usize = MAX_SAMPLES_PER_FRAME * 15;
+
+/// Like [Decoder](struct.Decoder.html), but pulls its MP3 bytes from a `R: Read`
+/// instead of requiring the whole stream up front, refilling an internal buffer
+/// as frames are consumed. Behind the "std" feature.
+pub struct ReadDecoder<R> {
+    reader: R,
+    eof: bool,
+
+    // Unconsumed bytes live at `buf[pos..]`; `pos` is dropped (and the tail moved
+    // down) the next time the buffer is topped up, rather than on every frame.
+    buf: Vec<u8>,
+    pos: usize,
+
+    ffi_frame: ffi::mp3dec_frame_info_t,
+    instance: ffi::mp3dec_t,
+    pcm: [Sample; MAX_SAMPLES_PER_FRAME],
+}
+
+impl<R: Read> ReadDecoder<R> {
+    /// Creates a decoder pulling MP3 bytes from `reader` as needed.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            eof: false,
+
+            buf: Vec::new(),
+            pos: 0,
+
+            ffi_frame: unsafe { mem::zeroed() },
+            instance: unsafe {
+                let mut decoder: ffi::mp3dec_t = mem::zeroed();
+                ffi::mp3dec_init(&mut decoder);
+                decoder
+            },
+            pcm: [Default::default(); MAX_SAMPLES_PER_FRAME],
+        }
+    }
+
+    /// Reads the next frame, if available, refilling the internal buffer from the
+    /// reader as needed. Like [Decoder::next_frame](struct.Decoder.html#method.next_frame),
+    /// non-sample data (ex. ID3) is skipped over automatically.
+    ///
+    /// Returns `Ok(None)` once the reader is exhausted and no full frame remains.
+    pub fn next_frame(&mut self) -> io::Result<Option<Frame>> {
+        loop {
+            self.fill_buf()?;
+            let unconsumed_len = self.buf.len() - self.pos;
+            if unconsumed_len == 0 {
+                return Ok(None);
+            }
+
+            let frame_len = unconsumed_len.min(c_int::max_value() as usize);
+            let data_ptr = unsafe { self.buf.as_ptr().add(self.pos) };
+            let samples = unsafe {
+                ffi::mp3dec_decode_frame(
+                    &mut self.instance,
+                    data_ptr,
+                    frame_len as c_int,
+                    self.pcm.as_mut_ptr(),
+                    &mut self.ffi_frame,
+                ) as u32
+            };
+            let frame_bytes = self.ffi_frame.frame_bytes as usize;
+
+            if frame_bytes == 0 {
+                // Not enough bytes resident for minimp3 to make progress; `fill_buf`
+                // already guaranteed a full frame unless the reader is truly exhausted.
+                return Ok(None);
+            }
+            self.pos += frame_bytes;
+
+            if samples > 0 {
+                return Ok(Some(Frame {
+                    bitrate: self.ffi_frame.bitrate_kbps as u32,
+                    channels: self.ffi_frame.channels as u32,
+                    mpeg_layer: self.ffi_frame.layer as u32,
+                    samples: unsafe {
+                        self.pcm
+                            .get_unchecked(..(samples * self.ffi_frame.channels as u32) as usize)
+                    },
+                    sample_rate: self.ffi_frame.hz as u32,
+                    sample_count: samples,
+                    source: unsafe { slice::from_raw_parts(data_ptr, frame_bytes) },
+                }));
+            }
+            // Non-audio bytes (ID3, junk, ...): already consumed above, loop for the next frame.
+        }
+    }
+
+    // Drops already-consumed bytes and tops the buffer back up to `HIGH_WATER`
+    // bytes resident, unless at least `LOW_WATER` bytes are still unconsumed or EOF
+    // was already observed.
+    fn fill_buf(&mut self) -> io::Result<()> {
+        if self.eof || self.buf.len() - self.pos >= LOW_WATER {
+            return Ok(());
+        }
+
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+
+        while self.buf.len() < HIGH_WATER {
+            let start = self.buf.len();
+            self.buf.resize(HIGH_WATER, 0);
+            match self.reader.read(&mut self.buf[start..]) {
+                Ok(0) => {
+                    self.buf.truncate(start);
+                    self.eof = true;
+                    break;
+                }
+                Ok(n) => self.buf.truncate(start + n),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => self.buf.truncate(start),
+                Err(e) => {
+                    self.buf.truncate(start);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec as StdVec;
+
+    // Reader that hands back one fixed chunk per `read` call, then `Ok(0)` (EOF) forever after.
+    struct ChunkedReader {
+        chunks: StdVec<&'static [u8]>,
+    }
+
+    impl Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.chunks.is_empty() {
+                return Ok(0);
+            }
+            let chunk = self.chunks.remove(0);
+            let n = chunk.len().min(buf.len());
+            buf[..n].copy_from_slice(&chunk[..n]);
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn fill_buf_flushes_eof_once_reader_is_exhausted() {
+        let mut dec = ReadDecoder::new(ChunkedReader { chunks: StdVec::from([b"abc".as_ref()]) });
+        dec.fill_buf().unwrap();
+        assert!(dec.eof);
+        assert_eq!(dec.buf.len() - dec.pos, 3);
+    }
+
+    #[test]
+    fn fill_buf_skips_refill_while_above_low_water() {
+        let mut dec = ReadDecoder::new(ChunkedReader { chunks: StdVec::new() });
+        dec.buf = StdVec::from([0u8; LOW_WATER]);
+        dec.pos = 0;
+        dec.fill_buf().unwrap();
+        // Unconsumed bytes were already at LOW_WATER, so no refill (and no EOF read) happened.
+        assert!(!dec.eof);
+        assert_eq!(dec.buf.len(), LOW_WATER);
+    }
+}